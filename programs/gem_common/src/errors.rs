@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("minimum staking period has not passed yet")]
+    MinStakingNotPassed,
+
+    #[msg("cooldown period has not passed yet")]
+    CooldownNotPassed,
+
+    #[msg("reward over-allocated: would pay out more than has ever been funded")]
+    RewardOverAllocated,
+}
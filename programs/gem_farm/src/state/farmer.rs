@@ -12,6 +12,54 @@ pub enum FarmerState {
     PendingCooldown,
 }
 
+// --------------------------------------- events
+//
+// structured, machine-readable counterparts to the msg!() logs below, so indexers/RPC
+// consumers can reconstruct a per-farmer reward ledger without parsing log strings
+
+#[event]
+pub struct StakeBegan {
+    pub farm: Pubkey,
+    pub identity: Pubkey,
+    pub gems_staked: u64,
+    pub min_staking_ends_ts: u64,
+    pub now_ts: u64,
+}
+
+#[event]
+pub struct CooldownBegan {
+    pub farm: Pubkey,
+    pub identity: Pubkey,
+    pub gems_unstaked: u64,
+    pub cooldown_ends_ts: u64,
+    pub now_ts: u64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub farm: Pubkey,
+    pub identity: Pubkey,
+    pub now_ts: u64,
+}
+
+#[event]
+pub struct RewardAccrued {
+    pub farm: Pubkey,
+    pub identity: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub now_ts: u64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub farm: Pubkey,
+    pub identity: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub now_ts: u64,
+}
+
 #[repr(C)]
 #[account]
 #[derive(Debug)]
@@ -55,29 +103,61 @@ impl Farmer {
         self.reward_a.fixed_rate.reset_staking_cycle();
         self.reward_b.fixed_rate.reset_staking_cycle();
 
+        // a fresh staking cycle starts loyal; it's broken the moment the farmer
+        // passes through PendingCooldown again
+        self.reward_a.fixed_rate.mark_loyal(now_ts);
+        self.reward_b.fixed_rate.mark_loyal(now_ts);
+
+        emit!(StakeBegan {
+            farm: self.farm,
+            identity: self.identity,
+            gems_staked: self.gems_staked,
+            min_staking_ends_ts: self.min_staking_ends_ts,
+            now_ts,
+        });
+
         Ok(())
     }
 
+    // the slash is applied here, at cooldown entry, rather than at end_cooldown: this is
+    // the moment the farmer commits to leaving, and outstanding_reward() is still measured
+    // against the farm they're actually exiting
     pub fn end_staking_begin_cooldown(
         &mut self,
         now_ts: u64,
         cooldown_period_sec: u64,
-    ) -> Result<u64, ProgramError> {
+        slash_schedule: &SlashSchedule,
+    ) -> Result<(u64, u64, u64), ProgramError> {
         if !self.can_end_staking(now_ts) {
             return Err(ErrorCode::MinStakingNotPassed.into());
         }
 
+        let (slashed_a, slashed_b) = self.slash_on_early_exit(now_ts, slash_schedule)?;
+
         self.state = FarmerState::PendingCooldown;
         let gems_unstaked = self.gems_staked;
         self.gems_staked = 0; //no rewards will accrue during cooldown period
         self.cooldown_ends_ts = now_ts.try_add(cooldown_period_sec)?;
 
+        // entering cooldown breaks any uninterrupted-staking streak
+        self.reward_a.fixed_rate.reset_loyalty();
+        self.reward_b.fixed_rate.reset_loyalty();
+
         msg!(
             "{} gems now cooling down for {}",
             gems_unstaked,
             self.identity
         );
-        Ok(gems_unstaked)
+
+        emit!(CooldownBegan {
+            farm: self.farm,
+            identity: self.identity,
+            gems_unstaked,
+            cooldown_ends_ts: self.cooldown_ends_ts,
+            now_ts,
+        });
+
+        Ok((gems_unstaked, slashed_a, slashed_b))
     }
 
     pub fn end_cooldown(&mut self, now_ts: u64) -> ProgramResult {
@@ -95,6 +175,62 @@ impl Farmer {
             "gems now unstaked and available for withdrawal for {}",
             self.identity
         );
+
+        emit!(Unstaked {
+            farm: self.farm,
+            identity: self.identity,
+            now_ts,
+        });
+
+        Ok(())
+    }
+
+    // moves a farmer to a new farm without waiting out a cooldown: gated on the same
+    // min-staking check as end_staking_begin_cooldown. `new_farmer` is a freshly
+    // initialized Farmer PDA on the destination farm (seeded by [new_farm, identity],
+    // distinct from this one, seeded by [self.farm, identity]) - this is a two-account
+    // move, never a reassignment of `farm` on an existing PDA. Fixed-rate reward earned
+    // so far is crystallized right here: it was funded by *this* farm's pot, so it stays
+    // on this record, still claimable via claim_reward after the move. `new_farmer`
+    // receives the gem count and begins a brand new staking cycle, recomputing
+    // min_staking_ends_ts from the destination farm's own config
+    pub fn change_staking_target(
+        &mut self,
+        new_farmer: &mut Farmer,
+        now_ts: u64,
+        new_min_staking_period_sec: u64,
+        reward_a_mint: Pubkey,
+        reward_b_mint: Pubkey,
+    ) -> ProgramResult {
+        if !self.can_end_staking(now_ts) {
+            return Err(ErrorCode::MinStakingNotPassed.into());
+        }
+
+        let gems_staked = self.gems_staked;
+
+        self.reward_a
+            .accrue_fixed_rate(now_ts, gems_staked, self.farm, self.identity, reward_a_mint)?;
+        self.reward_b
+            .accrue_fixed_rate(now_ts, gems_staked, self.farm, self.identity, reward_b_mint)?;
+
+        // vacate the source: outstanding reward stays right here, still claimable
+        self.state = FarmerState::Unstaked;
+        self.gems_staked = 0;
+        self.min_staking_ends_ts = 0;
+        self.cooldown_ends_ts = 0;
+        self.reward_a.fixed_rate.reset_loyalty();
+        self.reward_b.fixed_rate.reset_loyalty();
+
+        new_farmer.begin_staking(new_min_staking_period_sec, now_ts, gems_staked)?;
+
+        msg!(
+            "{} migrated {} gems from farm {} to farm {}, outstanding reward preserved on the source farm",
+            self.identity,
+            gems_staked,
+            self.farm,
+            new_farmer.farm
+        );
+
         Ok(())
     }
 
@@ -105,6 +241,71 @@ impl Farmer {
     fn can_end_cooldown(&self, now_ts: u64) -> bool {
         now_ts >= self.cooldown_ends_ts
     }
+
+    // applies a farm-configured early-exit penalty to outstanding reward on both mints;
+    // called from end_staking_begin_cooldown, before gems_staked is zeroed out
+    pub fn slash_on_early_exit(
+        &mut self,
+        now_ts: u64,
+        schedule: &SlashSchedule,
+    ) -> Result<(u64, u64), ProgramError> {
+        let elapsed_sec = now_ts.try_sub(self.min_staking_ends_ts).unwrap_or(0);
+        let forfeit_bps = schedule.forfeit_bps_at(elapsed_sec);
+
+        let slashed_a = self.reward_a.slash(forfeit_bps)?;
+        let slashed_b = self.reward_b.slash(forfeit_bps)?;
+
+        msg!(
+            "slashed {} bps of outstanding reward ({} reward_a, {} reward_b) from {} for early exit",
+            forfeit_bps,
+            slashed_a,
+            slashed_b,
+            self.identity
+        );
+
+        Ok((slashed_a, slashed_b))
+    }
+}
+
+// a single time bucket of the early-exit penalty curve
+#[repr(C)]
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SlashBucket {
+    // this bucket applies while elapsed time since min_staking_ends_ts is below this, in sec
+    pub ends_after_sec: u64,
+
+    // reward forfeited while inside this bucket, in bps (1/100th of a percent)
+    pub forfeit_bps: u16,
+}
+
+// time-decaying early-exit penalty: a farmer forfeits `forfeit_bps` of their outstanding
+// reward if they begin cooldown within `ends_after_sec` of min_staking_ends_ts, decaying
+// to 0 once promised_duration_sec has elapsed since then
+#[repr(C)]
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SlashSchedule {
+    pub buckets: [SlashBucket; 5],
+
+    // once this many seconds have passed since min_staking_ends_ts, no slash applies
+    pub promised_duration_sec: u64,
+}
+
+impl SlashSchedule {
+    // picks the tightest-fitting bucket (the smallest ends_after_sec that still covers
+    // elapsed_sec), so the result doesn't depend on `buckets` being stored in any
+    // particular order
+    fn forfeit_bps_at(&self, elapsed_sec: u64) -> u16 {
+        if elapsed_sec >= self.promised_duration_sec {
+            return 0;
+        }
+
+        self.buckets
+            .iter()
+            .filter(|b| elapsed_sec < b.ends_after_sec)
+            .min_by_key(|b| b.ends_after_sec)
+            .map(|b| b.forfeit_bps)
+            .unwrap_or(0)
+    }
 }
 
 #[repr(C)]
@@ -126,14 +327,104 @@ impl FarmerReward {
         self.accrued_reward.try_sub(self.paid_out_reward)
     }
 
-    pub fn claim_reward(&mut self, pot_balance: u64) -> Result<u64, ProgramError> {
+    // `farm_wide_paid_and_outstanding` is the farm-wide sum of paid_out_reward +
+    // outstanding_reward() across every farmer, as it stands going into this claim.
+    // Claiming only moves value from outstanding to paid_out, so that sum is invariant
+    // under `to_claim` itself - we're asserting the state we're claiming *out of* is
+    // still solvent, not re-deriving it with this claim added on top (which would
+    // double count and falsely reject a farm funded exactly to its allocation)
+    pub fn claim_reward(
+        &mut self,
+        pot_balance: u64,
+        total_rewards_funded: u64,
+        farm_wide_paid_and_outstanding: u64,
+        farm: Pubkey,
+        identity: Pubkey,
+        mint: Pubkey,
+        now_ts: u64,
+    ) -> Result<u64, ProgramError> {
+        assert_not_over_allocated(farm_wide_paid_and_outstanding, total_rewards_funded)?;
+
         let outstanding = self.outstanding_reward()?;
         let to_claim = std::cmp::min(outstanding, pot_balance);
 
         self.paid_out_reward.try_add_assign(to_claim)?;
 
+        emit!(RewardClaimed {
+            farm,
+            identity,
+            mint,
+            amount: to_claim,
+            now_ts,
+        });
+
         Ok(to_claim)
     }
+
+    // forfeits up to `forfeit_bps` of outstanding reward, never dipping accrued_reward
+    // below paid_out_reward (outstanding already excludes everything paid out so far)
+    pub fn slash(&mut self, forfeit_bps: u16) -> Result<u64, ProgramError> {
+        if forfeit_bps == 0 {
+            return Ok(0);
+        }
+
+        let outstanding = self.outstanding_reward()?;
+        let to_slash = std::cmp::min(
+            ((outstanding as u128)
+                .try_mul(forfeit_bps as u128)?
+                .try_floor_div(10_000)?) as u64,
+            outstanding,
+        );
+
+        self.accrued_reward.try_sub_assign(to_slash)?;
+
+        Ok(to_slash)
+    }
+
+    // credits whatever fixed-rate reward has accrued up to now_ts and advances the
+    // fixed-rate clock. This is the primary fixed-rate accrual path - called both by the
+    // farm's periodic reward-update and, before a migration, by change_staking_target, so
+    // the amount isn't lost when the farmer leaves this farm
+    pub fn accrue_fixed_rate(
+        &mut self,
+        now_ts: u64,
+        gems: u64,
+        farm: Pubkey,
+        identity: Pubkey,
+        mint: Pubkey,
+    ) -> ProgramResult {
+        let newly_accrued = self.fixed_rate.newly_accrued_reward(now_ts, gems)?;
+        self.accrued_reward.try_add_assign(newly_accrued)?;
+        self.fixed_rate.last_updated_ts = now_ts;
+
+        if newly_accrued > 0 {
+            emit!(RewardAccrued {
+                farm,
+                identity,
+                mint,
+                amount: newly_accrued,
+                now_ts,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// the over-allocation invariant: what a farm has paid out plus what it still owes,
+// summed across every farmer, can never exceed what has actually been funded into the
+// pot. Asserted in claim_reward only: the accrual paths in this module
+// (FarmerReward::accrue_fixed_rate, FarmerVariableRateReward::accrue) do not check it,
+// so an over-allocation created at accrual time is caught at the next claim
+pub fn assert_not_over_allocated(
+    farm_wide_paid_and_outstanding: u64,
+    total_rewards_funded: u64,
+) -> ProgramResult {
+    if farm_wide_paid_and_outstanding > total_rewards_funded {
+        return Err(ErrorCode::RewardOverAllocated.into());
+    }
+
+    Ok(())
 }
 
 #[repr(C)]
@@ -144,6 +435,67 @@ pub struct FarmerVariableRateReward {
     pub last_recorded_accrued_reward_per_gem: Number128,
 }
 
+impl FarmerVariableRateReward {
+    // advances this farmer's view of the per-gem accrued reward using the farm's latest
+    // PointValue for the funding window, folding in whatever dust was carried over from
+    // the previous window *before* the floor-division happens (not after), so the
+    // remainder is never silently dropped. Returns the new dust to carry into the next
+    // window
+    pub fn accrue(
+        &mut self,
+        point_value: &PointValue,
+        carried_dust: u128,
+    ) -> Result<u128, ProgramError> {
+        let (accrued_per_gem_delta, dust) = point_value.accrued_per_gem(carried_dust)?;
+
+        // PointValue deals in POINT_VALUE_SCALE, which is defined as Number128's own
+        // fixed-point scale precisely so this delta can be folded straight in below with
+        // no separate reconciliation step
+        self.last_recorded_accrued_reward_per_gem = self
+            .last_recorded_accrued_reward_per_gem
+            .try_add(Number128::new(accrued_per_gem_delta))?;
+
+        Ok(dust)
+    }
+}
+
+// PointValue is scaled in the same fixed-point units Number128 uses internally, so an
+// accrued_per_gem() delta can be added directly into a Number128 with no rescaling
+pub const POINT_VALUE_SCALE: u128 = Number128::SCALE;
+
+// mirrors the integer `PointValue` technique from Solana's stake rewards: advancing the
+// variable rate for a window is done purely in integers, so nothing is lost or
+// double-counted to floating point error
+#[repr(C)]
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PointValue {
+    // reward funded into the pot during this window
+    pub rewards: u64,
+
+    // total gems staked across the farm during this window
+    pub points: u128,
+}
+
+impl PointValue {
+    // folds `carried_dust` (left over from the previous window, already in
+    // POINT_VALUE_SCALE units) into the numerator *before* the floor-division, so it's
+    // actually redistributed rather than being dropped. Returns (accrued_per_gem delta,
+    // the new remainder to carry into the next window)
+    pub fn accrued_per_gem(&self, carried_dust: u128) -> Result<(u128, u128), ProgramError> {
+        if self.points == 0 {
+            return Ok((0, carried_dust));
+        }
+
+        let scaled_rewards = (self.rewards as u128)
+            .try_mul(POINT_VALUE_SCALE)?
+            .try_add(carried_dust)?;
+        let accrued_per_gem = scaled_rewards.try_floor_div(self.points)?;
+        let dust = scaled_rewards.try_sub(accrued_per_gem.try_mul(self.points)?)?;
+
+        Ok((accrued_per_gem, dust))
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct FarmerFixedRateReward {
@@ -156,6 +508,17 @@ pub struct FarmerFixedRateReward {
     pub promised_duration: u64,
 
     pub reward_counted_as_accrued: u64,
+
+    // timestamp from which the farmer has been continuously staked, never having
+    // passed through FarmerState::PendingCooldown since
+    pub loyal_since_ts: u64,
+
+    // whether the streak above is still unbroken
+    pub is_loyal: bool,
+
+    // whether the lump-sum loyalty bonus for this streak has already been credited, so
+    // it's paid exactly once regardless of how often newly_accrued_reward is polled
+    pub loyalty_bonus_paid: bool,
 }
 
 impl FarmerFixedRateReward {
@@ -163,6 +526,30 @@ impl FarmerFixedRateReward {
         self.begin_staking_ts.try_add(self.promised_duration)
     }
 
+    pub fn mark_loyal(&mut self, now_ts: u64) {
+        self.loyal_since_ts = now_ts;
+        self.is_loyal = true;
+        self.loyalty_bonus_paid = false;
+    }
+
+    pub fn reset_loyalty(&mut self) {
+        self.loyal_since_ts = 0;
+        self.is_loyal = false;
+        self.loyalty_bonus_paid = false;
+    }
+
+    // has the farmer stayed continuously staked for a full promised duration?
+    pub fn loyalty_bonus_earned(&self, now_ts: u64) -> bool {
+        if !self.is_loyal {
+            return false;
+        }
+
+        match now_ts.try_sub(self.loyal_since_ts) {
+            Ok(elapsed) => elapsed >= self.promised_duration,
+            Err(_) => false,
+        }
+    }
+
     // pub fn capped_accrued_duration(&self, now_ts: u64) -> Result<u64, ProgramError> {
     //     let upper_bound_ts = std::cmp::min(now_ts, self.graduation_time()?);
     //     upper_bound_ts.try_sub(self.begin_staking_ts)
@@ -192,11 +579,28 @@ impl FarmerFixedRateReward {
         self.promised_schedule.calc_amount(start_from, end_at, gems)
     }
 
-    pub fn newly_accrued_reward(&self, now_ts: u64, gems: u64) -> Result<u64, ProgramError> {
+    pub fn newly_accrued_reward(&mut self, now_ts: u64, gems: u64) -> Result<u64, ProgramError> {
         let start_from = self.last_updated_ts.try_sub(self.begin_staking_ts)?;
         let end_at = self
             .upper_bound_ts(now_ts)?
             .try_sub(self.begin_staking_ts)?;
-        self.promised_schedule.calc_amount(start_from, end_at, gems)
+        let base_amount = self.promised_schedule.calc_amount(start_from, end_at, gems)?;
+
+        // paid once, as a lump sum covering the whole cycle, the first time accrual
+        // crosses the loyalty threshold - never as a per-call multiplier, so the total
+        // payout doesn't depend on how often this gets polled
+        if self.loyalty_bonus_paid || !self.loyalty_bonus_earned(now_ts) {
+            return Ok(base_amount);
+        }
+        self.loyalty_bonus_paid = true;
+
+        let full_cycle_amount = self
+            .promised_schedule
+            .calc_amount(0, self.promised_duration, gems)?;
+        let bonus = ((full_cycle_amount as u128)
+            .try_mul(self.promised_schedule.loyalty_bonus_bps as u128)?
+            .try_floor_div(10_000)?) as u64;
+
+        base_amount.try_add(bonus)
     }
 }
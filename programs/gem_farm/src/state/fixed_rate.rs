@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use gem_common::*;
+
+pub const MAX_FIXED_REWARD_TIERS: usize = 10;
+
+// a single time-based tier: covers up to (but not including) `ends_after_sec` seconds
+// since a farmer's begin_staking_ts, paying `reward_rate_per_gem` per gem per second
+// while inside it
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct FixedRateRewardTier {
+    pub ends_after_sec: u64,
+
+    pub reward_rate_per_gem: u64,
+}
+
+// a farm-configured, tiered fixed-rate schedule, plus a bonus for farmers who stay
+// continuously staked (never passing through FarmerState::PendingCooldown) for a full
+// promised_duration
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct FixedRateSchedule {
+    pub tiers: [FixedRateRewardTier; MAX_FIXED_REWARD_TIERS],
+
+    // common denominator reward_rate_per_gem is expressed over (eg 1_000_000 for micro-units)
+    pub denominator: u64,
+
+    // bonus paid on top of the base schedule amount for farmers who complete a full
+    // promised_duration without ever breaking their staking streak, in bps
+    pub loyalty_bonus_bps: u16,
+}
+
+impl FixedRateSchedule {
+    // reward accrued for `gems` staked between [start_from, end_at), both expressed as
+    // seconds since begin_staking_ts, integrating across whichever tiers the interval spans
+    pub fn calc_amount(
+        &self,
+        start_from: u64,
+        end_at: u64,
+        gems: u64,
+    ) -> Result<u64, ProgramError> {
+        let mut amount = 0u64;
+        let mut lower_bound = start_from;
+
+        for tier in self.tiers.iter() {
+            if lower_bound >= end_at {
+                break;
+            }
+            if tier.ends_after_sec <= lower_bound {
+                continue;
+            }
+
+            let upper_bound = std::cmp::min(tier.ends_after_sec, end_at);
+            let duration = upper_bound.try_sub(lower_bound)?;
+
+            let tier_amount = ((duration as u128)
+                .try_mul(tier.reward_rate_per_gem as u128)?
+                .try_mul(gems as u128)?
+                .try_floor_div(self.denominator as u128)?) as u64;
+
+            amount.try_add_assign(tier_amount)?;
+            lower_bound = upper_bound;
+        }
+
+        Ok(amount)
+    }
+}
+
+// a farm-owned account wrapping the fixed-rate schedule and the mint it pays out in, so
+// operators can update the schedule without touching the Farm account itself
+#[repr(C)]
+#[account]
+#[derive(Debug)]
+pub struct FixedRateReward {
+    pub reward_mint: Pubkey,
+
+    pub schedule: FixedRateSchedule,
+}
+
+impl FixedRateReward {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // reward_mint
+        (MAX_FIXED_REWARD_TIERS * (8 + 8)) + // schedule.tiers
+        8 + // schedule.denominator
+        2; // schedule.loyalty_bonus_bps
+}